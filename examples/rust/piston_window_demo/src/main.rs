@@ -1,22 +1,771 @@
+extern crate glfw_window;
+extern crate glutin_window;
+extern crate image;
 extern crate piston_window;
+extern crate sdl2_window;
 
+use glfw_window::GlfwWindow;
+use glutin_window::GlutinWindow;
 use piston_window::*;
+use sdl2_window::Sdl2Window;
 
-fn main() {
-    let mut window: PistonWindow = WindowSettings::new("Hello Piston!", [640, 480])
-        .exit_on_esc(true)
-        .build()
-        .unwrap();
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Tile {
+    Empty,
+    Wall,
+}
+
+struct Level {
+    width: usize,
+    height: usize,
+    tiles: Vec<Tile>,
+}
+
+impl Level {
+    fn new(width: usize, height: usize, tiles: Vec<Tile>) -> Level {
+        assert_eq!(width * height, tiles.len());
+        Level { width, height, tiles }
+    }
+
+    fn at(&self, x: i32, y: i32) -> Tile {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return Tile::Wall;
+        }
+        self.tiles[y as usize * self.width + x as usize]
+    }
+
+    // A small box with a couple of interior walls, just enough to see depth.
+    fn demo() -> Level {
+        let w = 8;
+        let h = 8;
+        let mut tiles = vec![Tile::Empty; w * h];
+        for x in 0..w {
+            tiles[x] = Tile::Wall;
+            tiles[(h - 1) * w + x] = Tile::Wall;
+        }
+        for y in 0..h {
+            tiles[y * w] = Tile::Wall;
+            tiles[y * w + (w - 1)] = Tile::Wall;
+        }
+        tiles[3 * w + 3] = Tile::Wall;
+        tiles[3 * w + 4] = Tile::Wall;
+        Level::new(w, h, tiles)
+    }
+}
+
+struct Player {
+    px: f64,
+    py: f64,
+    // View direction and camera plane, Wolfenstein-style.
+    dir_x: f64,
+    dir_y: f64,
+    plane_x: f64,
+    plane_y: f64,
+}
+
+impl Player {
+    fn new(px: f64, py: f64) -> Player {
+        Player {
+            px,
+            py,
+            dir_x: -1.0,
+            dir_y: 0.0,
+            plane_x: 0.0,
+            plane_y: 0.66,
+        }
+    }
+}
+
+// Casts one ray per screen column with DDA and returns the color and
+// the screen-space rectangle of the resulting wall slice.
+fn cast_column(level: &Level, player: &Player, x: u32, screen_width: f64, screen_height: f64) -> ([f32; 4], [f64; 4]) {
+    let camera_x = 2.0 * x as f64 / screen_width - 1.0;
+    let ray_dir_x = player.dir_x + player.plane_x * camera_x;
+    let ray_dir_y = player.dir_y + player.plane_y * camera_x;
+
+    let mut map_x = player.px as i32;
+    let mut map_y = player.py as i32;
+
+    let delta_dist_x = if ray_dir_x == 0.0 { f64::INFINITY } else { (1.0 / ray_dir_x).abs() };
+    let delta_dist_y = if ray_dir_y == 0.0 { f64::INFINITY } else { (1.0 / ray_dir_y).abs() };
+
+    let (step_x, mut side_dist_x) = if ray_dir_x < 0.0 {
+        (-1, (player.px - map_x as f64) * delta_dist_x)
+    } else {
+        (1, (map_x as f64 + 1.0 - player.px) * delta_dist_x)
+    };
+    let (step_y, mut side_dist_y) = if ray_dir_y < 0.0 {
+        (-1, (player.py - map_y as f64) * delta_dist_y)
+    } else {
+        (1, (map_y as f64 + 1.0 - player.py) * delta_dist_y)
+    };
+
+    let mut hit_y_side;
+    loop {
+        if side_dist_x < side_dist_y {
+            side_dist_x += delta_dist_x;
+            map_x += step_x;
+            hit_y_side = false;
+        } else {
+            side_dist_y += delta_dist_y;
+            map_y += step_y;
+            hit_y_side = true;
+        }
+        if level.at(map_x, map_y) == Tile::Wall {
+            break;
+        }
+    }
+
+    // Perpendicular distance, not Euclidean, to avoid the fish-eye effect.
+    let perp_dist = if hit_y_side {
+        (map_y as f64 - player.py + (1 - step_y) as f64 / 2.0) / ray_dir_y
+    } else {
+        (map_x as f64 - player.px + (1 - step_x) as f64 / 2.0) / ray_dir_x
+    };
+
+    let line_height = screen_height / perp_dist;
+    let draw_start = (-line_height / 2.0 + screen_height / 2.0).max(0.0);
+    let draw_height = line_height.min(screen_height);
+
+    // Shade y-side hits darker so the two wall faces read as distinct.
+    let shade = if hit_y_side { 0.6 } else { 1.0 };
+    let color = [0.8 * shade as f32, 0.1 * shade as f32, 0.1 * shade as f32, 1.0];
+    (color, [x as f64, draw_start, 1.0, draw_height])
+}
+
+// Decouples a game's logic from whichever physical key triggers it.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Action {
+    MoveForward,
+    MoveBackward,
+    StrafeLeft,
+    StrafeRight,
+    Quit,
+}
+
+// A plain HashMap rather than a match so a custom binding set could
+// eventually be loaded without code changes.
+struct Keymap(std::collections::HashMap<Key, Action>);
+
+impl Keymap {
+    fn default_bindings() -> Keymap {
+        let mut map = std::collections::HashMap::new();
+        map.insert(Key::W, Action::MoveForward);
+        map.insert(Key::Up, Action::MoveForward);
+        map.insert(Key::S, Action::MoveBackward);
+        map.insert(Key::Down, Action::MoveBackward);
+        map.insert(Key::A, Action::StrafeLeft);
+        map.insert(Key::D, Action::StrafeRight);
+        map.insert(Key::Escape, Action::Quit);
+        Keymap(map)
+    }
+
+    fn action_for(&self, key: Key) -> Option<Action> {
+        self.0.get(&key).copied()
+    }
+}
+
+struct InputState {
+    keymap: Keymap,
+    held: std::collections::HashSet<Action>,
+    mouse_delta: [f64; 2],
+}
+
+impl InputState {
+    fn new() -> InputState {
+        InputState {
+            keymap: Keymap::default_bindings(),
+            held: std::collections::HashSet::new(),
+            mouse_delta: [0.0, 0.0],
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) {
+        if let Some(Button::Keyboard(key)) = event.press_args() {
+            if let Some(action) = self.keymap.action_for(key) {
+                self.held.insert(action);
+            }
+        }
+        if let Some(Button::Keyboard(key)) = event.release_args() {
+            if let Some(action) = self.keymap.action_for(key) {
+                self.held.remove(&action);
+            }
+        }
+        if let Some(motion) = event.mouse_relative_args() {
+            self.mouse_delta[0] += motion[0];
+            self.mouse_delta[1] += motion[1];
+        }
+    }
 
+    fn is_held(&self, action: Action) -> bool {
+        self.held.contains(&action)
+    }
+
+    // Mouse deltas are per-tick; clear them once the update that consumed
+    // them has run so the next tick starts from zero.
+    fn end_tick(&mut self) {
+        self.mouse_delta = [0.0, 0.0];
+    }
+}
+
+// Update runs on a fixed timestep, independent of how often frames render.
+trait Game {
+    fn update(&mut self, args: UpdateArgs, input: &InputState);
+    fn render(&mut self, args: RenderArgs, c: &Context, g: &mut G2d);
+    fn input(&mut self, event: &Event);
+}
+
+// Generic over the window back-end since the loop body only needs the
+// common `Window` + piston_window rendering plumbing.
+fn run<W: Window + OpenGLWindow, G: Game>(window: &mut PistonWindow<W>, game: &mut G) {
+    let mut input = InputState::new();
     while let Some(e) = window.next() {
-        window.draw_2d(&e, |c, g, _| {
-            clear([1.0; 4], g);
-            rectangle(
-                [1.0, 0.0, 0.0, 1.0], // Red color
-                [50.0, 50.0, 100.0, 100.0], // x, y, w, h
-                c.transform,
-                g,
-            );
+        match e {
+            Event::Loop(Loop::Update(args)) => {
+                game.update(args, &input);
+                input.end_tick();
+            }
+            Event::Loop(Loop::Render(args)) => {
+                window.draw_2d(&e, |c, g2d, _| game.render(args, &c, g2d));
+            }
+            Event::Input(..) => {
+                input.handle_event(&e);
+                game.input(&e);
+            }
+            _ => {}
+        }
+        if input.is_held(Action::Quit) {
+            break;
+        }
+    }
+}
+
+struct RaycasterGame {
+    level: Level,
+    player: Player,
+    width: u32,
+    height: u32,
+}
+
+const MOVE_SPEED: f64 = 3.0;
+const ROTATE_SPEED: f64 = 0.002;
+
+impl Game for RaycasterGame {
+    fn update(&mut self, args: UpdateArgs, input: &InputState) {
+        let (sin, cos) = (self.player.dir_y, self.player.dir_x);
+        let step = MOVE_SPEED * args.dt;
+
+        if input.is_held(Action::MoveForward) {
+            self.player.px += cos * step;
+            self.player.py += sin * step;
+        }
+        if input.is_held(Action::MoveBackward) {
+            self.player.px -= cos * step;
+            self.player.py -= sin * step;
+        }
+        if input.is_held(Action::StrafeLeft) {
+            self.player.px -= sin * step;
+            self.player.py += cos * step;
+        }
+        if input.is_held(Action::StrafeRight) {
+            self.player.px += sin * step;
+            self.player.py -= cos * step;
+        }
+
+        let rot = -input.mouse_delta[0] * ROTATE_SPEED;
+        if rot != 0.0 {
+            let (dir_x, dir_y) = (self.player.dir_x, self.player.dir_y);
+            let (plane_x, plane_y) = (self.player.plane_x, self.player.plane_y);
+            self.player.dir_x = dir_x * rot.cos() - dir_y * rot.sin();
+            self.player.dir_y = dir_x * rot.sin() + dir_y * rot.cos();
+            self.player.plane_x = plane_x * rot.cos() - plane_y * rot.sin();
+            self.player.plane_y = plane_x * rot.sin() + plane_y * rot.cos();
+        }
+    }
+
+    fn render(&mut self, _args: RenderArgs, c: &Context, g: &mut G2d) {
+        clear([0.0, 0.0, 0.0, 1.0], g);
+        for x in 0..self.width {
+            let (color, rect) = cast_column(&self.level, &self.player, x, self.width as f64, self.height as f64);
+            rectangle(color, rect, c.transform, g);
+        }
+    }
+
+    fn input(&mut self, _event: &Event) {}
+}
+
+// Minimal xorshift64 PRNG (13/7/17 shifts) with a multiplied output,
+// seeded from the clock, just to seed the Life
+// grid without pulling in a dependency for one random fill.
+struct Rng(u64);
+
+impl Rng {
+    fn seeded() -> Rng {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0x2545F4914F6CDD1D)
+            | 1;
+        Rng(seed)
+    }
+
+    fn next_bool(&mut self) -> bool {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x.wrapping_mul(0x2545F4914F6CDD1D) & 1 == 1
+    }
+}
+
+struct LifeGame {
+    cols: usize,
+    rows: usize,
+    cell_size: f64,
+    grid: Vec<bool>,
+    back: Vec<bool>,
+    cursor: [f64; 2],
+}
+
+impl LifeGame {
+    fn new(width: u32, height: u32, cell_size: f64) -> LifeGame {
+        let cols = (width as f64 / cell_size).ceil() as usize;
+        let rows = (height as f64 / cell_size).ceil() as usize;
+        let mut rng = Rng::seeded();
+        let grid = (0..cols * rows).map(|_| rng.next_bool()).collect();
+        LifeGame {
+            cols,
+            rows,
+            cell_size,
+            grid,
+            back: vec![false; cols * rows],
+            cursor: [0.0, 0.0],
+        }
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        y * self.cols + x
+    }
+
+    // Wraps around the edges, so gliders leaving one side re-enter the other.
+    fn live_neighbors(&self, x: usize, y: usize) -> u8 {
+        let mut count = 0;
+        for dy in [self.rows - 1, 0, 1] {
+            for dx in [self.cols - 1, 0, 1] {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                let nx = (x + dx) % self.cols;
+                let ny = (y + dy) % self.rows;
+                if self.grid[self.index(nx, ny)] {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    fn toggle_at_cursor(&mut self) {
+        let x = (self.cursor[0] / self.cell_size) as usize;
+        let y = (self.cursor[1] / self.cell_size) as usize;
+        if x < self.cols && y < self.rows {
+            let i = self.index(x, y);
+            self.grid[i] = !self.grid[i];
+        }
+    }
+}
+
+impl Game for LifeGame {
+    fn update(&mut self, _args: UpdateArgs, _input: &InputState) {
+        for y in 0..self.rows {
+            for x in 0..self.cols {
+                let i = self.index(x, y);
+                let alive = self.grid[i];
+                let neighbors = self.live_neighbors(x, y);
+                self.back[i] = matches!((alive, neighbors), (true, 2) | (true, 3) | (false, 3));
+            }
+        }
+        std::mem::swap(&mut self.grid, &mut self.back);
+    }
+
+    fn render(&mut self, _args: RenderArgs, c: &Context, g: &mut G2d) {
+        clear([0.0, 0.0, 0.0, 1.0], g);
+        for y in 0..self.rows {
+            for x in 0..self.cols {
+                if self.grid[self.index(x, y)] {
+                    let rect = [x as f64 * self.cell_size, y as f64 * self.cell_size, self.cell_size, self.cell_size];
+                    rectangle([0.1, 0.9, 0.3, 1.0], rect, c.transform, g);
+                }
+            }
+        }
+    }
+
+    fn input(&mut self, event: &Event) {
+        if let Some(pos) = event.mouse_cursor_args() {
+            self.cursor = pos;
+        }
+        if let Some(Button::Mouse(MouseButton::Left)) = event.press_args() {
+            self.toggle_at_cursor();
+        }
+    }
+}
+
+const MANDELBROT_ITERATIONS: u32 = 256;
+const MANDELBROT_THREADS: usize = 4;
+
+#[derive(Clone, Copy)]
+struct View {
+    x_min: f64,
+    x_max: f64,
+    y_min: f64,
+    y_max: f64,
+}
+
+impl View {
+    fn default_for(width: u32, height: u32) -> View {
+        let aspect = height as f64 / width as f64;
+        View {
+            x_min: -2.5,
+            x_max: 1.0,
+            y_min: -1.75 * aspect,
+            y_max: 1.75 * aspect,
+        }
+    }
+
+    fn width(&self) -> f64 {
+        self.x_max - self.x_min
+    }
+
+    fn height(&self) -> f64 {
+        self.y_max - self.y_min
+    }
+}
+
+// Smooth-colors one escape-time sample so bands don't show up between
+// adjacent iteration counts.
+fn mandelbrot_color(cx: f64, cy: f64) -> [u8; 4] {
+    let (mut zx, mut zy) = (0.0, 0.0);
+    let mut i = 0;
+    while i < MANDELBROT_ITERATIONS && zx * zx + zy * zy <= 4.0 {
+        let new_zx = zx * zx - zy * zy + cx;
+        zy = 2.0 * zx * zy + cy;
+        zx = new_zx;
+        i += 1;
+    }
+    if i == MANDELBROT_ITERATIONS {
+        return [0, 0, 0, 255];
+    }
+    let mag = (zx * zx + zy * zy).sqrt();
+    let smooth = i as f64 + 1.0 - (mag.ln()).ln() / std::f64::consts::LN_2;
+    let t = smooth / MANDELBROT_ITERATIONS as f64;
+    let r = (9.0 * (1.0 - t) * t * t * t * 255.0) as u8;
+    let g = (15.0 * (1.0 - t) * (1.0 - t) * t * t * 255.0) as u8;
+    let b = (8.5 * (1.0 - t) * (1.0 - t) * (1.0 - t) * t * 255.0) as u8;
+    [r, g, b, 255]
+}
+
+struct MandelbrotGame {
+    width: u32,
+    height: u32,
+    view: View,
+    buffer: Vec<u8>,
+    texture: G2dTexture,
+    texture_context: G2dTextureContext,
+    dirty: bool,
+    dragging: bool,
+    cursor: [f64; 2],
+}
+
+impl MandelbrotGame {
+    fn new<W: Window>(window: &mut PistonWindow<W>, width: u32, height: u32) -> MandelbrotGame {
+        let buffer = vec![0u8; (width * height * 4) as usize];
+        let mut texture_context = window.create_texture_context();
+        let texture = Texture::from_image(&mut texture_context, &buffer_to_image(&buffer, width, height), &TextureSettings::new()).unwrap();
+        MandelbrotGame {
+            width,
+            height,
+            view: View::default_for(width, height),
+            buffer,
+            texture_context,
+            texture,
+            dirty: true,
+            dragging: false,
+            cursor: [0.0, 0.0],
+        }
+    }
+
+    // Splits the rows across MANDELBROT_THREADS worker threads; each owns
+    // its own slice of scanlines so there's no synchronization overhead.
+    fn recompute(&mut self) {
+        let width = self.width;
+        let height = self.height;
+        let view = self.view;
+        let rows_per_thread = (height as usize + MANDELBROT_THREADS - 1) / MANDELBROT_THREADS;
+
+        let chunks: Vec<Vec<u8>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..MANDELBROT_THREADS)
+                .map(|t| {
+                    let row_start = t * rows_per_thread;
+                    let row_end = (row_start + rows_per_thread).min(height as usize);
+                    scope.spawn(move || {
+                        let mut chunk = Vec::with_capacity((row_end.saturating_sub(row_start)) * width as usize * 4);
+                        for y in row_start..row_end {
+                            let cy = view.y_min + (y as f64 / height as f64) * view.height();
+                            for x in 0..width {
+                                let cx = view.x_min + (x as f64 / width as f64) * view.width();
+                                chunk.extend_from_slice(&mandelbrot_color(cx, cy));
+                            }
+                        }
+                        chunk
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
         });
+
+        self.buffer.clear();
+        for chunk in chunks {
+            self.buffer.extend_from_slice(&chunk);
+        }
+        self.dirty = false;
+    }
+
+    fn zoom(&mut self, cursor: [f64; 2], factor: f64) {
+        let cx = self.view.x_min + (cursor[0] / self.width as f64) * self.view.width();
+        let cy = self.view.y_min + (cursor[1] / self.height as f64) * self.view.height();
+        self.view.x_min = cx + (self.view.x_min - cx) * factor;
+        self.view.x_max = cx + (self.view.x_max - cx) * factor;
+        self.view.y_min = cy + (self.view.y_min - cy) * factor;
+        self.view.y_max = cy + (self.view.y_max - cy) * factor;
+        self.dirty = true;
+    }
+
+    fn pan(&mut self, dx: f64, dy: f64) {
+        let wx = dx / self.width as f64 * self.view.width();
+        let wy = dy / self.height as f64 * self.view.height();
+        self.view.x_min -= wx;
+        self.view.x_max -= wx;
+        self.view.y_min -= wy;
+        self.view.y_max -= wy;
+        self.dirty = true;
+    }
+}
+
+fn buffer_to_image(buffer: &[u8], width: u32, height: u32) -> image::RgbaImage {
+    image::RgbaImage::from_raw(width, height, buffer.to_vec()).unwrap()
+}
+
+impl Game for MandelbrotGame {
+    fn update(&mut self, _args: UpdateArgs, _input: &InputState) {}
+
+    fn render(&mut self, _args: RenderArgs, c: &Context, g: &mut G2d) {
+        if self.dirty {
+            self.recompute();
+            self.texture.update(&mut self.texture_context, &buffer_to_image(&self.buffer, self.width, self.height)).unwrap();
+        }
+        clear([0.0, 0.0, 0.0, 1.0], g);
+        image(&self.texture, c.transform, g);
+    }
+
+    fn input(&mut self, event: &Event) {
+        if let Some(pos) = event.mouse_cursor_args() {
+            if self.dragging {
+                self.pan(pos[0] - self.cursor[0], pos[1] - self.cursor[1]);
+            }
+            self.cursor = pos;
+        }
+        if let Some(Button::Mouse(MouseButton::Left)) = event.press_args() {
+            self.dragging = true;
+        }
+        if let Some(Button::Mouse(MouseButton::Left)) = event.release_args() {
+            self.dragging = false;
+        }
+        if let Some(scroll) = event.mouse_scroll_args() {
+            let factor = if scroll[1] > 0.0 { 0.9 } else { 1.0 / 0.9 };
+            self.zoom(self.cursor, factor);
+        }
+    }
+}
+
+// Lets a user switch windowing back-ends without recompiling, useful
+// when one back-end fails context creation on a given machine.
+#[derive(Clone, Copy)]
+enum Backend {
+    Glutin,
+    Sdl2,
+    Glfw,
+}
+
+impl Backend {
+    fn from_flag(flag: &str) -> Backend {
+        match flag {
+            "sdl2" => Backend::Sdl2,
+            "glfw" => Backend::Glfw,
+            _ => Backend::Glutin,
+        }
+    }
+}
+
+fn run_mode<W: Window + OpenGLWindow>(window: &mut PistonWindow<W>, mode: &str, width: u32, height: u32) {
+    match mode {
+        "life" => {
+            let mut game = LifeGame::new(width, height, 10.0);
+            run(window, &mut game);
+        }
+        "mandelbrot" => {
+            let mut game = MandelbrotGame::new(window, width, height);
+            run(window, &mut game);
+        }
+        _ => {
+            let mut game = RaycasterGame {
+                level: Level::demo(),
+                player: Player::new(4.5, 4.5),
+                width,
+                height,
+            };
+            run(window, &mut game);
+        }
+    }
+}
+
+// Parses argv (excluding argv[0]) into the mode positional and the
+// `--backend <name>` flag, skipping the flag's value when scanning for
+// the mode so `--backend sdl2 life` and `life --backend sdl2` agree.
+fn parse_args(args: &[String]) -> (String, Backend) {
+    let backend_flag_at = args.iter().position(|a| a == "--backend");
+    let mode = args
+        .iter()
+        .enumerate()
+        .find(|(i, a)| !a.starts_with("--") && Some(i.wrapping_sub(1)) != backend_flag_at)
+        .map(|(_, a)| a.clone())
+        .unwrap_or_else(|| "raycast".to_string());
+    let backend = backend_flag_at
+        .and_then(|i| args.get(i + 1))
+        .map(|flag| Backend::from_flag(flag))
+        .unwrap_or(Backend::Glutin);
+    (mode, backend)
+}
+
+fn main() {
+    let width = 640;
+    let height = 480;
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (mode, backend) = parse_args(&args);
+
+    let settings = WindowSettings::new("Hello Piston!", [width, height]).exit_on_esc(true);
+
+    match backend {
+        Backend::Glutin => {
+            let mut window: PistonWindow<GlutinWindow> = settings.build().unwrap();
+            run_mode(&mut window, &mode, width, height);
+        }
+        Backend::Sdl2 => {
+            let mut window: PistonWindow<Sdl2Window> = settings.build().unwrap();
+            run_mode(&mut window, &mode, width, height);
+        }
+        Backend::Glfw => {
+            let mut window: PistonWindow<GlfwWindow> = settings.build().unwrap();
+            run_mode(&mut window, &mode, width, height);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_at_treats_out_of_bounds_as_wall() {
+        let level = Level::demo();
+        assert_eq!(level.at(-1, 0), Tile::Wall);
+        assert_eq!(level.at(0, -1), Tile::Wall);
+        assert_eq!(level.at(8, 0), Tile::Wall);
+        assert_eq!(level.at(0, 8), Tile::Wall);
+    }
+
+    #[test]
+    fn cast_column_hits_x_side_border_wall_straight_on() {
+        // Center column (camera_x == 0) fires the ray straight along
+        // player.dir, so it walks row y=4 until it hits the x=0 border wall.
+        let level = Level::demo();
+        let player = Player::new(4.5, 4.5);
+        let (color, rect) = cast_column(&level, &player, 1, 2.0, 100.0);
+
+        let expected_perp_dist = 3.5;
+        let expected_line_height = 100.0 / expected_perp_dist;
+        assert!((rect[3] - expected_line_height).abs() < 1e-9);
+        // x-side hit, so the unshaded (full-strength) wall color.
+        assert_eq!(color, [0.8, 0.1, 0.1, 1.0]);
+    }
+
+    fn life_game_from_grid(cols: usize, rows: usize, live: &[(usize, usize)]) -> LifeGame {
+        let mut grid = vec![false; cols * rows];
+        for &(x, y) in live {
+            grid[y * cols + x] = true;
+        }
+        LifeGame {
+            cols,
+            rows,
+            cell_size: 1.0,
+            grid,
+            back: vec![false; cols * rows],
+            cursor: [0.0, 0.0],
+        }
+    }
+
+    #[test]
+    fn blinker_oscillates_between_horizontal_and_vertical() {
+        // A blinker placed away from the edges, so wraparound neighbors
+        // don't come into play for this step.
+        let mut game = life_game_from_grid(5, 5, &[(1, 2), (2, 2), (3, 2)]);
+        game.update(UpdateArgs { dt: 0.0 }, &InputState::new());
+        let alive: Vec<(usize, usize)> = (0..5)
+            .flat_map(|y| (0..5).map(move |x| (x, y)))
+            .filter(|&(x, y)| game.grid[game.index(x, y)])
+            .collect();
+        assert_eq!(alive, vec![(2, 1), (2, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn live_neighbors_wraps_around_grid_edges() {
+        // Neighbors at (cols - 1, rows - 1), (0, rows - 1) and (cols - 1, 0)
+        // should all count as adjacent to (0, 0) on a toroidal grid.
+        let game = life_game_from_grid(3, 3, &[(2, 2), (0, 2), (2, 0)]);
+        assert_eq!(game.live_neighbors(0, 0), 3);
+    }
+
+    fn args(strs: &[&str]) -> Vec<String> {
+        strs.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_args_takes_backend_value_before_mode() {
+        let (mode, backend) = parse_args(&args(&["--backend", "sdl2", "life"]));
+        assert_eq!(mode, "life");
+        assert!(matches!(backend, Backend::Sdl2));
+    }
+
+    #[test]
+    fn parse_args_takes_mode_before_backend_flag() {
+        let (mode, backend) = parse_args(&args(&["life", "--backend", "glfw"]));
+        assert_eq!(mode, "life");
+        assert!(matches!(backend, Backend::Glfw));
+    }
+
+    #[test]
+    fn parse_args_defaults_mode_when_only_backend_given() {
+        let (mode, backend) = parse_args(&args(&["--backend", "sdl2"]));
+        assert_eq!(mode, "raycast");
+        assert!(matches!(backend, Backend::Sdl2));
+    }
+
+    #[test]
+    fn parse_args_defaults_backend_when_only_mode_given() {
+        let (mode, backend) = parse_args(&args(&["mandelbrot"]));
+        assert_eq!(mode, "mandelbrot");
+        assert!(matches!(backend, Backend::Glutin));
     }
 }